@@ -0,0 +1,373 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::character::complete::{char, digit1};
+use nom::combinator::{map, opt};
+use nom::multi::many0;
+use nom::sequence::tuple;
+use nom::IResult;
+
+use crate::{BTreeMap, Life, Rule, RwLock, World};
+
+/// The pattern interchange formats this binary can read and write.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Life106,
+    Life105,
+    Rle,
+    Plaintext,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "life106" => Ok(Format::Life106),
+            "life105" => Ok(Format::Life105),
+            "rle" => Ok(Format::Rle),
+            "plaintext" => Ok(Format::Plaintext),
+            other => Err(format!("unknown format '{}'", other)),
+        }
+    }
+}
+
+fn detect_format(lines: &[String]) -> Format {
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.eq("#Life 1.06") {
+            return Format::Life106;
+        }
+        if trimmed.eq("#Life 1.05") {
+            return Format::Life105;
+        }
+        if trimmed.starts_with('!') {
+            return Format::Plaintext;
+        }
+        if trimmed.starts_with('x') && trimmed.contains("rule") {
+            return Format::Rle;
+        }
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        // First non-header line without a recognizable marker: assume a bare
+        // coordinate pair, i.e. Life 1.06 without its header line.
+        return Format::Life106;
+    }
+    Format::Life106
+}
+
+fn new_world(rule: Rule) -> World {
+    World { map: RwLock::new(BTreeMap::new()), age: 0, rule }
+}
+
+/// Reads every line from `reader`, auto-detects the pattern format, and
+/// returns the populated `World` along with the format that was detected.
+/// `rule` is the rule explicitly requested on the command line, if any; RLE
+/// is the only format that can carry its own rule, and does so via its `x =
+/// …, rule = …` header, which wins only when the caller didn't ask for a
+/// specific rule explicitly.
+pub fn parse<R: BufRead>(reader: R, rule: Option<Rule>) -> (World, Format) {
+    let lines: Vec<String> = reader.lines().map_while(|line| line.ok()).collect();
+    let format = detect_format(&lines);
+    let world = match format {
+        Format::Life106 => parse_life_106(&lines, rule.unwrap_or_default()),
+        Format::Life105 => parse_life_105(&lines, rule.unwrap_or_default()),
+        Format::Rle => parse_rle(&lines, rule),
+        Format::Plaintext => parse_plaintext(&lines, rule.unwrap_or_default()),
+    };
+    (world, format)
+}
+
+fn parse_life_106(lines: &[String], rule: Rule) -> World {
+    let mut world = new_world(rule);
+
+    for line in lines {
+        let clean_line = line.trim();
+        if clean_line.is_empty() || clean_line.starts_with('#') {
+            continue;
+        }
+        let mut parts: Vec<&str> = clean_line.split(' ').collect();
+        let y_str = parts.pop().unwrap();
+        let x_str = parts.pop().unwrap();
+        let x_pos = i64::from_str(x_str).unwrap();
+        let y_pos = i64::from_str(y_str).unwrap();
+
+        world.add_life(Life::new(x_pos, y_pos));
+    }
+
+    world
+}
+
+fn parse_life_105(lines: &[String], rule: Rule) -> World {
+    let mut world = new_world(rule);
+
+    let mut origin_x: i64 = 0;
+    let mut origin_y: i64 = 0;
+    let mut row: i64 = 0;
+
+    for line in lines {
+        let clean_line = line.trim_end();
+        if clean_line.is_empty() || clean_line.eq("#Life 1.05") {
+            continue;
+        }
+        if let Some(rest) = clean_line.strip_prefix("#P") {
+            let mut coords = rest.split_whitespace();
+            origin_x = coords.next().and_then(|v| i64::from_str(v).ok()).unwrap_or(0);
+            origin_y = coords.next().and_then(|v| i64::from_str(v).ok()).unwrap_or(0);
+            row = 0;
+            continue;
+        }
+        if clean_line.starts_with('#') {
+            continue;
+        }
+        for (col, ch) in clean_line.chars().enumerate() {
+            if ch == '*' {
+                world.add_life(Life::new(origin_x + col as i64, origin_y + row));
+            }
+        }
+        row += 1;
+    }
+
+    world
+}
+
+fn parse_plaintext(lines: &[String], rule: Rule) -> World {
+    let mut world = new_world(rule);
+
+    let mut row: i64 = 0;
+    for line in lines {
+        if line.starts_with('!') {
+            continue;
+        }
+        for (col, ch) in line.chars().enumerate() {
+            if ch == 'O' {
+                world.add_life(Life::new(col as i64, row));
+            }
+        }
+        row += 1;
+    }
+
+    world
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RleToken {
+    Dead(u32),
+    Alive(u32),
+    EndOfLine(u32),
+}
+
+fn rle_count(input: &str) -> IResult<&str, u32> {
+    map(opt(digit1), |n: Option<&str>| n.map_or(1, |s| s.parse().unwrap_or(1)))(input)
+}
+
+fn rle_token(input: &str) -> IResult<&str, RleToken> {
+    alt((
+        map(tuple((rle_count, char('b'))), |(n, _)| RleToken::Dead(n)),
+        map(tuple((rle_count, char('o'))), |(n, _)| RleToken::Alive(n)),
+        map(tuple((rle_count, char('$'))), |(n, _)| RleToken::EndOfLine(n)),
+    ))(input)
+}
+
+fn rle_body(input: &str) -> IResult<&str, Vec<RleToken>> {
+    many0(rle_token)(input)
+}
+
+/// Pulls the `rule = ...` field out of an RLE header line such as
+/// `x = 3, y = 3, rule = B36/S23`, if present and parsable.
+fn extract_rle_rule(header: &str) -> Option<Rule> {
+    let (_, rest) = header.split_once("rule")?;
+    let (_, value) = rest.split_once('=')?;
+    Rule::from_str(value.trim().trim_end_matches(',')).ok()
+}
+
+fn parse_rle(lines: &[String], explicit_rule: Option<Rule>) -> World {
+    let mut header_rule = None;
+    let mut body = String::new();
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.starts_with('x') {
+            // Header line, e.g. "x = 3, y = 3, rule = B3/S23" — dimensions
+            // aren't needed since cells are stored sparsely, but the rule
+            // is, when the caller didn't request one explicitly.
+            header_rule = extract_rle_rule(trimmed);
+            continue;
+        }
+        body.push_str(trimmed);
+    }
+
+    let mut world = new_world(explicit_rule.or(header_rule).unwrap_or_default());
+
+    let body = body.trim_end_matches('!');
+    let tokens = match rle_body(body) {
+        Ok((_, tokens)) => tokens,
+        Err(_) => Vec::new(),
+    };
+
+    let mut x: i64 = 0;
+    let mut y: i64 = 0;
+    for token in tokens {
+        match token {
+            RleToken::Dead(n) => x += n as i64,
+            RleToken::Alive(n) => {
+                for _ in 0..n {
+                    world.add_life(Life::new(x, y));
+                    x += 1;
+                }
+            }
+            RleToken::EndOfLine(n) => {
+                y += n as i64;
+                x = 0;
+            }
+        }
+    }
+
+    world
+}
+
+/// Serializes `world` into the given output format.
+pub fn serialize(world: &World, format: Format) -> String {
+    match format {
+        Format::Life106 => to_life_106(world),
+        Format::Life105 => to_life_105(world),
+        Format::Rle => to_rle(world),
+        Format::Plaintext => to_plaintext(world),
+    }
+}
+
+fn live_cells(world: &World) -> Vec<Life> {
+    let x_map = world.map.read().unwrap();
+    let mut cells = Vec::new();
+    for (_x_key, y_map) in x_map.iter() {
+        for (_y_key, life) in y_map.iter() {
+            cells.push(*life);
+        }
+    }
+    cells
+}
+
+fn to_life_106(world: &World) -> String {
+    let mut out = String::from("#Life 1.06\n");
+    for life in live_cells(world) {
+        out.push_str(&format!("{} {}\n", life.x_pos, life.y_pos));
+    }
+    out
+}
+
+fn to_life_105(world: &World) -> String {
+    let cells = live_cells(world);
+    let mut out = String::from("#Life 1.05\n");
+    if cells.is_empty() {
+        return out;
+    }
+
+    let min_x = cells.iter().map(|l| l.x_pos).min().unwrap();
+    let min_y = cells.iter().map(|l| l.y_pos).min().unwrap();
+    let max_x = cells.iter().map(|l| l.x_pos).max().unwrap();
+    let max_y = cells.iter().map(|l| l.y_pos).max().unwrap();
+
+    out.push_str(&format!("#P {} {}\n", min_x, min_y));
+    let mut alive = std::collections::HashSet::new();
+    for life in &cells {
+        alive.insert((life.x_pos, life.y_pos));
+    }
+    for y in min_y..=max_y {
+        let mut row = String::new();
+        for x in min_x..=max_x {
+            row.push(if alive.contains(&(x, y)) { '*' } else { '.' });
+        }
+        out.push_str(&row);
+        out.push('\n');
+    }
+
+    out
+}
+
+fn to_plaintext(world: &World) -> String {
+    let cells = live_cells(world);
+    let mut out = String::from("!life-rust export\n");
+    if cells.is_empty() {
+        return out;
+    }
+
+    let min_x = cells.iter().map(|l| l.x_pos).min().unwrap();
+    let min_y = cells.iter().map(|l| l.y_pos).min().unwrap();
+    let max_x = cells.iter().map(|l| l.x_pos).max().unwrap();
+    let max_y = cells.iter().map(|l| l.y_pos).max().unwrap();
+
+    let mut alive = std::collections::HashSet::new();
+    for life in &cells {
+        alive.insert((life.x_pos, life.y_pos));
+    }
+    for y in min_y..=max_y {
+        let mut row = String::new();
+        for x in min_x..=max_x {
+            row.push(if alive.contains(&(x, y)) { 'O' } else { '.' });
+        }
+        out.push_str(&row);
+        out.push('\n');
+    }
+
+    out
+}
+
+fn to_rle(world: &World) -> String {
+    let cells = live_cells(world);
+    if cells.is_empty() {
+        return format!("x = 0, y = 0, rule = {}\n!\n", world.rule);
+    }
+
+    let min_x = cells.iter().map(|l| l.x_pos).min().unwrap();
+    let min_y = cells.iter().map(|l| l.y_pos).min().unwrap();
+    let max_x = cells.iter().map(|l| l.x_pos).max().unwrap();
+    let max_y = cells.iter().map(|l| l.y_pos).max().unwrap();
+    let width = max_x - min_x + 1;
+    let height = max_y - min_y + 1;
+
+    let mut alive = std::collections::HashSet::new();
+    for life in &cells {
+        alive.insert((life.x_pos, life.y_pos));
+    }
+
+    let mut out = format!("x = {}, y = {}, rule = {}\n", width, height, world.rule);
+    let mut body = String::new();
+    for y in min_y..=max_y {
+        let mut run_char = None;
+        let mut run_len = 0u32;
+        for x in min_x..=max_x {
+            let c = if alive.contains(&(x, y)) { 'o' } else { 'b' };
+            if Some(c) == run_char {
+                run_len += 1;
+            } else {
+                if let Some(rc) = run_char {
+                    push_run(&mut body, run_len, rc);
+                }
+                run_char = Some(c);
+                run_len = 1;
+            }
+        }
+        if let Some(rc) = run_char {
+            push_run(&mut body, run_len, rc);
+        }
+        body.push('$');
+    }
+    body.push('!');
+    out.push_str(&body);
+    out.push('\n');
+    out
+}
+
+fn push_run(body: &mut String, run_len: u32, run_char: char) {
+    if run_len > 1 {
+        body.push_str(&run_len.to_string());
+    }
+    body.push(run_char);
+}
@@ -0,0 +1,499 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use crate::{BTreeMap, Life, Rule, RwLock, World};
+
+pub type NodeRef = Rc<Node>;
+
+/// A node in the Hashlife macrocell quadtree. `Leaf` is a single cell;
+/// `Inner` is a square of side `2^level` built from four `level - 1`
+/// quadrants. Identical subtrees are always the same `Rc`, so structural
+/// equality is pointer equality.
+pub enum Node {
+    Leaf(bool),
+    Inner {
+        level: u8,
+        nw: NodeRef,
+        ne: NodeRef,
+        sw: NodeRef,
+        se: NodeRef,
+        population: u64,
+    },
+}
+
+impl Node {
+    pub fn level(&self) -> u8 {
+        match self {
+            Node::Leaf(_) => 0,
+            Node::Inner { level, .. } => *level,
+        }
+    }
+
+    pub fn population(&self) -> u64 {
+        match self {
+            Node::Leaf(alive) => *alive as u64,
+            Node::Inner { population, .. } => *population,
+        }
+    }
+}
+
+fn node_key(node: &NodeRef) -> usize {
+    Rc::as_ptr(node) as usize
+}
+
+/// A canonicalizing table of macrocells plus a memoized `result` cache,
+/// parameterized by the `Rule` patterns advance under.
+pub struct Universe {
+    rule: Rule,
+    dead_leaf: NodeRef,
+    alive_leaf: NodeRef,
+    join_cache: std::cell::RefCell<HashMap<(usize, usize, usize, usize), NodeRef>>,
+    result_cache: std::cell::RefCell<HashMap<usize, NodeRef>>,
+    empty_cache: std::cell::RefCell<Vec<NodeRef>>,
+}
+
+impl Universe {
+    pub fn new(rule: Rule) -> Universe {
+        Universe {
+            rule,
+            dead_leaf: Rc::new(Node::Leaf(false)),
+            alive_leaf: Rc::new(Node::Leaf(true)),
+            join_cache: std::cell::RefCell::new(HashMap::new()),
+            result_cache: std::cell::RefCell::new(HashMap::new()),
+            empty_cache: std::cell::RefCell::new(Vec::new()),
+        }
+    }
+
+    fn leaf(&self, alive: bool) -> NodeRef {
+        if alive { self.alive_leaf.clone() } else { self.dead_leaf.clone() }
+    }
+
+    /// Interns a level-`k` node built from four level-`k - 1` quadrants,
+    /// returning the canonical, already-built node if this exact
+    /// combination has been seen before.
+    fn join(&self, nw: NodeRef, ne: NodeRef, sw: NodeRef, se: NodeRef) -> NodeRef {
+        let key = (node_key(&nw), node_key(&ne), node_key(&sw), node_key(&se));
+        if let Some(existing) = self.join_cache.borrow().get(&key) {
+            return existing.clone();
+        }
+
+        let level = nw.level() + 1;
+        let population = nw.population() + ne.population() + sw.population() + se.population();
+        let node = Rc::new(Node::Inner { level, nw, ne, sw, se, population });
+        self.join_cache.borrow_mut().insert(key, node.clone());
+        node
+    }
+
+    /// The canonical empty node of the given level.
+    fn empty(&self, level: u8) -> NodeRef {
+        if level == 0 {
+            return self.leaf(false);
+        }
+        {
+            let cache = self.empty_cache.borrow();
+            if let Some(node) = cache.get(level as usize) {
+                return node.clone();
+            }
+        }
+        let child = self.empty(level - 1);
+        let node = self.join(child.clone(), child.clone(), child.clone(), child);
+        let mut cache = self.empty_cache.borrow_mut();
+        while cache.len() <= level as usize {
+            cache.push(node.clone());
+        }
+        node
+    }
+
+    fn child(node: &NodeRef, which: u8) -> NodeRef {
+        match &**node {
+            Node::Inner { nw, ne, sw, se, .. } => match which {
+                0 => nw.clone(),
+                1 => ne.clone(),
+                2 => sw.clone(),
+                _ => se.clone(),
+            },
+            Node::Leaf(_) => unreachable!("leaves have no children"),
+        }
+    }
+
+    /// Pads `node` with a ring of empty cells one level larger, keeping the
+    /// existing pattern centered so a macro-step cannot run off the edge.
+    fn expand(&self, node: &NodeRef) -> NodeRef {
+        let border = self.empty(node.level().saturating_sub(1));
+        let nw = self.join(border.clone(), border.clone(), border.clone(), Self::child(node, 0));
+        let ne = self.join(border.clone(), border.clone(), Self::child(node, 1), border.clone());
+        let sw = self.join(border.clone(), Self::child(node, 2), border.clone(), border.clone());
+        let se = self.join(Self::child(node, 3), border.clone(), border.clone(), border);
+        self.join(nw, ne, sw, se)
+    }
+
+    fn centered_horizontal(&self, w: &NodeRef, e: &NodeRef) -> NodeRef {
+        self.join(Self::child(w, 1), Self::child(e, 0), Self::child(w, 3), Self::child(e, 2))
+    }
+
+    fn centered_vertical(&self, n: &NodeRef, s: &NodeRef) -> NodeRef {
+        self.join(Self::child(n, 2), Self::child(n, 3), Self::child(s, 0), Self::child(s, 1))
+    }
+
+    /// The centered level-`k - 1` subnode of a level-`k` node — a pure
+    /// structural crop with no time advance.
+    fn centered_subnode(&self, node: &NodeRef) -> NodeRef {
+        let nw = Self::child(node, 0);
+        let ne = Self::child(node, 1);
+        let sw = Self::child(node, 2);
+        let se = Self::child(node, 3);
+        self.join(Self::child(&nw, 3), Self::child(&ne, 2), Self::child(&sw, 1), Self::child(&se, 0))
+    }
+
+    /// Advances a 4x4 (level-2) node one generation by direct neighbor
+    /// counting, returning the advanced center as a level-1 (2x2) node.
+    fn base_result(&self, node: &NodeRef) -> NodeRef {
+        let mut grid = [[false; 4]; 4];
+        for (q, (ox, oy)) in [(0u8, (0, 0)), (1, (2, 0)), (2, (0, 2)), (3, (2, 2))] {
+            let quadrant = Self::child(node, q);
+            for (i, which) in [0u8, 1, 2, 3].into_iter().enumerate() {
+                if let Node::Leaf(alive) = *Self::child(&quadrant, which) {
+                    let (dx, dy) = (i % 2, i / 2);
+                    grid[oy + dy][ox + dx] = alive;
+                }
+            }
+        }
+
+        let mut next = [[false; 2]; 2];
+        for (cy, row) in [1usize, 2].into_iter().enumerate() {
+            for (cx, col) in [1usize, 2].into_iter().enumerate() {
+                let mut count = 0u8;
+                for dy in -1i64..=1 {
+                    for dx in -1i64..=1 {
+                        if dx == 0 && dy == 0 {
+                            continue;
+                        }
+                        let ny = (row as i64 + dy) as usize;
+                        let nx = (col as i64 + dx) as usize;
+                        if grid[ny][nx] {
+                            count += 1;
+                        }
+                    }
+                }
+                next[cy][cx] = if grid[row][col] { self.rule.survives(count) } else { self.rule.is_born(count) };
+            }
+        }
+
+        self.join(self.leaf(next[0][0]), self.leaf(next[0][1]), self.leaf(next[1][0]), self.leaf(next[1][1]))
+    }
+
+    /// Returns the memoized result of `node`: its center square, half its
+    /// side length, advanced `2^(level - 2)` generations.
+    fn result(&self, node: &NodeRef) -> NodeRef {
+        let key = node_key(node);
+        if let Some(cached) = self.result_cache.borrow().get(&key) {
+            return cached.clone();
+        }
+
+        let level = node.level();
+        let result = if level == 2 {
+            self.base_result(node)
+        } else {
+            let nw = Self::child(node, 0);
+            let ne = Self::child(node, 1);
+            let sw = Self::child(node, 2);
+            let se = Self::child(node, 3);
+
+            let n00 = nw.clone();
+            let n01 = self.centered_horizontal(&nw, &ne);
+            let n02 = ne.clone();
+            let n10 = self.centered_vertical(&nw, &sw);
+            let n11 = self.centered_subnode(node);
+            let n12 = self.centered_vertical(&ne, &se);
+            let n20 = sw.clone();
+            let n21 = self.centered_horizontal(&sw, &se);
+            let n22 = se.clone();
+
+            let r00 = self.result(&n00);
+            let r01 = self.result(&n01);
+            let r02 = self.result(&n02);
+            let r10 = self.result(&n10);
+            let r11 = self.result(&n11);
+            let r12 = self.result(&n12);
+            let r20 = self.result(&n20);
+            let r21 = self.result(&n21);
+            let r22 = self.result(&n22);
+
+            let final_nw = self.result(&self.join(r00, r01.clone(), r10.clone(), r11.clone()));
+            let final_ne = self.result(&self.join(r01, r02, r11.clone(), r12.clone()));
+            let final_sw = self.result(&self.join(r10, r11.clone(), r20, r21.clone()));
+            let final_se = self.result(&self.join(r11, r12, r21, r22));
+
+            self.join(final_nw, final_ne, final_sw, final_se)
+        };
+
+        self.result_cache.borrow_mut().insert(key, result.clone());
+        result
+    }
+
+    /// Advances the centered half-size subnode of `node` by exactly `n`
+    /// generations, where `0 <= n <= 2^(level(node) - 2)`. The endpoints
+    /// recurse into operations that are already exact — `n == 0` is the
+    /// pure structural `centered_subnode` crop, and `n` at the maximum is
+    /// the memoized `result` — so only a genuinely partial jump needs the
+    /// general case. That case advances the 9 overlapping child windows by
+    /// `min(n, half)` generations, joins the adjacent ones into the 4
+    /// quadrants a full `result` would have produced at that halfway point,
+    /// then (if `n` is more than half) advances those quadrants by the
+    /// remainder. This is the same halving `result` itself uses internally
+    /// to turn a level's fixed "full" jump into two half jumps, just
+    /// stopped wherever `n` runs out instead of always running to the end.
+    fn advance(&self, node: &NodeRef, n: u64) -> NodeRef {
+        let level = node.level();
+        let max_n = 1u64 << level.saturating_sub(2);
+        if n == 0 {
+            return self.centered_subnode(node);
+        }
+        if n == max_n {
+            return self.result(node);
+        }
+
+        let nw = Self::child(node, 0);
+        let ne = Self::child(node, 1);
+        let sw = Self::child(node, 2);
+        let se = Self::child(node, 3);
+
+        let n00 = nw.clone();
+        let n01 = self.centered_horizontal(&nw, &ne);
+        let n02 = ne.clone();
+        let n10 = self.centered_vertical(&nw, &sw);
+        let n11 = self.centered_subnode(node);
+        let n12 = self.centered_vertical(&ne, &se);
+        let n20 = sw.clone();
+        let n21 = self.centered_horizontal(&sw, &se);
+        let n22 = se.clone();
+
+        let half = max_n / 2;
+        let g1 = n.min(half);
+        let r00 = self.advance(&n00, g1);
+        let r01 = self.advance(&n01, g1);
+        let r02 = self.advance(&n02, g1);
+        let r10 = self.advance(&n10, g1);
+        let r11 = self.advance(&n11, g1);
+        let r12 = self.advance(&n12, g1);
+        let r20 = self.advance(&n20, g1);
+        let r21 = self.advance(&n21, g1);
+        let r22 = self.advance(&n22, g1);
+
+        let g2 = n - g1;
+        let q_nw = self.join(r00, r01.clone(), r10.clone(), r11.clone());
+        let q_ne = self.join(r01, r02, r11.clone(), r12.clone());
+        let q_sw = self.join(r10, r11.clone(), r20, r21.clone());
+        let q_se = self.join(r11, r12, r21, r22);
+
+        let final_nw = self.advance(&q_nw, g2);
+        let final_ne = self.advance(&q_ne, g2);
+        let final_sw = self.advance(&q_sw, g2);
+        let final_se = self.advance(&q_se, g2);
+
+        self.join(final_nw, final_ne, final_sw, final_se)
+    }
+
+    /// Advances `node` (whose north-west corner sits at world coordinates
+    /// `(x0, y0)`) by `generations`, any count up to 2^62 — `advance` handles
+    /// non-power-of-two jumps natively, so `step` only needs to find a
+    /// level big enough to represent the count and hand off to it. Returns
+    /// the new root node together with the world coordinates of *its*
+    /// north-west corner, since the view always shrinks to the centered
+    /// half-size subnode regardless of how many generations were requested.
+    ///
+    /// The jump size is independent of `node`'s current level: if `node` is
+    /// too small to represent `generations` at all (its max jump,
+    /// `2^(level - 2)`, falls short), it is padded up via `expand` first.
+    /// If it is bigger than strictly needed, it is left alone — `advance`
+    /// can reach any generation count up to a node's max without first
+    /// cropping it down, which is what let a too-aggressive crop here
+    /// silently throw away live cells outside the crop in an earlier
+    /// version of this function.
+    pub fn step(&self, node: NodeRef, x0: i64, y0: i64, generations: u64) -> (NodeRef, i64, i64) {
+        // The smallest level whose max jump (2^(level - 2)) covers
+        // `generations`. Capped at level 64 so the shifts below never
+        // overflow, which bounds a single call to 2^62 generations; larger
+        // counts are not supported.
+        let mut target_level = 2u8;
+        while target_level < 64 && (1u64 << (target_level - 2)) < generations {
+            target_level += 1;
+        }
+
+        let mut node = node;
+        let mut x0 = x0;
+        let mut y0 = y0;
+
+        while node.level() < target_level {
+            let pad = 1i64 << (node.level() - 1);
+            node = self.expand(&node);
+            x0 -= pad;
+            y0 -= pad;
+        }
+
+        let center_offset = 1i64 << (node.level() - 2);
+        let advanced = self.advance(&node, generations);
+        (advanced, x0 + center_offset, y0 + center_offset)
+    }
+
+    fn build(&self, alive: &HashSet<(i64, i64)>, x0: i64, y0: i64, level: u8) -> NodeRef {
+        if level == 0 {
+            return self.leaf(alive.contains(&(x0, y0)));
+        }
+        let half = 1i64 << (level - 1);
+        let nw = self.build(alive, x0, y0, level - 1);
+        let ne = self.build(alive, x0 + half, y0, level - 1);
+        let sw = self.build(alive, x0, y0 + half, level - 1);
+        let se = self.build(alive, x0 + half, y0 + half, level - 1);
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Builds a macrocell quadtree from `world`'s sparse live cells,
+    /// centering the pattern in the universe with a margin on every side
+    /// at least as wide as the pattern itself, so a macro-step's
+    /// center-cropped `result` cannot clip live cells. Returns the root
+    /// node along with the world-space coordinates of its north-west
+    /// corner.
+    // Named after the `World` it builds from, not the `self`-free `from_*`
+    // constructor convention — it needs `&self` to intern nodes into this
+    // universe's tables.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_world(&self, world: &World) -> (NodeRef, i64, i64) {
+        let x_map = world.map.read().unwrap();
+        let mut alive = HashSet::new();
+        for (_x, y_map) in x_map.iter() {
+            for (_y, life) in y_map.iter() {
+                alive.insert((life.x_pos, life.y_pos));
+            }
+        }
+        drop(x_map);
+
+        if alive.is_empty() {
+            return (self.empty(2), 0, 0);
+        }
+
+        let min_x = alive.iter().map(|c| c.0).min().unwrap();
+        let max_x = alive.iter().map(|c| c.0).max().unwrap();
+        let min_y = alive.iter().map(|c| c.1).min().unwrap();
+        let max_y = alive.iter().map(|c| c.1).max().unwrap();
+        let width = max_x - min_x + 1;
+        let height = max_y - min_y + 1;
+        let span = width.max(height).max(1);
+
+        // Pick a universe at least 4x the pattern's span, so the bounding
+        // box can sit centered with a full span of empty margin all around.
+        let mut level = 2u8;
+        while (1i64 << level) < span * 4 {
+            level += 1;
+        }
+
+        let side = 1i64 << level;
+        let origin_x = min_x - (side - width) / 2;
+        let origin_y = min_y - (side - height) / 2;
+
+        let node = self.build(&alive, origin_x, origin_y, level);
+        (node, origin_x, origin_y)
+    }
+
+    fn collect_alive(&self, node: &NodeRef, x0: i64, y0: i64, level: u8, out: &mut Vec<(i64, i64)>) {
+        if node.population() == 0 {
+            return;
+        }
+        match &**node {
+            Node::Leaf(true) => out.push((x0, y0)),
+            Node::Leaf(false) => {}
+            Node::Inner { nw, ne, sw, se, .. } => {
+                let half = 1i64 << (level - 1);
+                self.collect_alive(nw, x0, y0, level - 1, out);
+                self.collect_alive(ne, x0 + half, y0, level - 1, out);
+                self.collect_alive(sw, x0, y0 + half, level - 1, out);
+                self.collect_alive(se, x0 + half, y0 + half, level - 1, out);
+            }
+        }
+    }
+
+    /// Converts a macrocell node back into a sparse `World`, positioned so
+    /// its north-west corner sits at `(x0, y0)`.
+    pub fn to_world(&self, node: &NodeRef, x0: i64, y0: i64, rule: Rule) -> World {
+        let mut cells = Vec::new();
+        self.collect_alive(node, x0, y0, node.level(), &mut cells);
+
+        let mut world = World { map: RwLock::new(BTreeMap::new()), age: 0, rule };
+        for (x, y) in cells {
+            world.add_life(Life::new(x, y));
+        }
+        world
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TimeBasedEntity;
+
+    fn glider_world() -> World {
+        let mut world = World { map: RwLock::new(BTreeMap::new()), age: 0, rule: Rule::default() };
+        for (x, y) in [(1i64, 0i64), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            world.add_life(Life::new(x, y));
+        }
+        world.initialize();
+        world
+    }
+
+    fn alive_cells(world: &World) -> HashSet<(i64, i64)> {
+        let x_map = world.map.read().unwrap();
+        let mut cells = HashSet::new();
+        for (_x, y_map) in x_map.iter() {
+            for (_y, life) in y_map.iter() {
+                cells.insert((life.x_pos, life.y_pos));
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn hashlife_matches_naive_stepper_one_generation() {
+        let mut naive = glider_world();
+        naive.tick();
+        let expected = alive_cells(&naive);
+
+        let universe = Universe::new(Rule::default());
+        let (root, x0, y0) = universe.from_world(&glider_world());
+        let (advanced, x0, y0) = universe.step(root, x0, y0, 1);
+        let actual = alive_cells(&universe.to_world(&advanced, x0, y0, Rule::default()));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn hashlife_matches_naive_stepper_several_generations() {
+        let mut naive = glider_world();
+        for _ in 0..4 {
+            naive.tick();
+        }
+        let expected = alive_cells(&naive);
+
+        let universe = Universe::new(Rule::default());
+        let (root, x0, y0) = universe.from_world(&glider_world());
+        let (advanced, x0, y0) = universe.step(root, x0, y0, 4);
+        let actual = alive_cells(&universe.to_world(&advanced, x0, y0, Rule::default()));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn hashlife_matches_naive_stepper_beyond_natural_level() {
+        let mut naive = glider_world();
+        for _ in 0..16 {
+            naive.tick();
+        }
+        let expected = alive_cells(&naive);
+
+        let universe = Universe::new(Rule::default());
+        let (root, x0, y0) = universe.from_world(&glider_world());
+        let (advanced, x0, y0) = universe.step(root, x0, y0, 16);
+        let actual = alive_cells(&universe.to_world(&advanced, x0, y0, Rule::default()));
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,12 +1,20 @@
 use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::io;
-use std::io::BufRead;
 use std::ops::Bound::Included;
 use std::process::exit;
 use std::str::FromStr;
 use std::sync::RwLock;
 
+mod formats;
+mod hashlife;
+mod parallel;
+mod persistence;
+mod rule;
+mod tui;
+
+use rule::Rule;
+
 trait TimeBasedEntity {
     fn tick(&mut self);
 }
@@ -26,7 +34,7 @@ impl Life {
     pub fn is_close_neighbor(&self, neighbor: &Life) -> bool {
         let dist_x = neighbor.x_pos - self.x_pos;
         let dist_y = neighbor.y_pos - self.y_pos;
-        dist_x >= -1 && dist_x <= 1 && dist_y >= -1 && dist_y <= 1
+        (-1..=1).contains(&dist_x) && (-1..=1).contains(&dist_y)
     }
 
     fn calculate_neighbor_coordinates(&self, pos: u8) -> Option<(i64, i64)> {
@@ -46,7 +54,8 @@ impl Life {
 
 struct World {
     map:RwLock<BTreeMap<i64, BTreeMap<i64, Life>>>,
-    age:u32
+    age:u32,
+    rule:Rule
 }
 
 impl Display for World {
@@ -54,10 +63,7 @@ impl Display for World {
         let x_map = self.map.read().unwrap();
         for (_x_key, x_value) in x_map.iter() {
             for (_y_key, life) in x_value.iter() {
-                match writeln!(f, "{} {}", life.x_pos, life.y_pos) {
-                    Ok(_) => {},
-                    Err(_) => {}
-                }
+                let _ = writeln!(f, "{} {}", life.x_pos, life.y_pos);
             }
         }
         Ok(())
@@ -70,14 +76,12 @@ impl World {
         let mut x_map = self.map.write().unwrap();
         match x_map.get_mut(&life.x_pos) {
             Some(y_map) => {
-                if !y_map.contains_key(&life.y_pos) {
-                    y_map.insert(life.y_pos.clone(), life);
-                }
+                y_map.entry(life.y_pos).or_insert(life);
             },
             None => {
                 let mut y_map: BTreeMap<i64, Life> = BTreeMap::new();
-                let x_pos = life.x_pos.clone();
-                y_map.insert(life.y_pos.clone(), life);
+                let x_pos = life.x_pos;
+                y_map.insert(life.y_pos, life);
                 x_map.insert(x_pos, y_map);
             }
         }
@@ -85,13 +89,8 @@ impl World {
 
     pub fn remove_life(&mut self, life: &Life) {
         let mut x_map = self.map.write().unwrap();
-        match x_map.get_mut(&life.x_pos) {
-            Some(y_map) => {
-                if y_map.contains_key(&life.y_pos) {
-                    y_map.remove(&life.y_pos);
-                }
-            },
-            None => { }
+        if let Some(y_map) = x_map.get_mut(&life.x_pos) {
+            y_map.remove(&life.y_pos);
         }
     }
 
@@ -101,18 +100,27 @@ impl World {
         let x_range = x_map.range((Included(start_x), Included(end_x)));
         for (_x_key, x_value) in x_range {
             for (_y_key, life) in x_value.range((Included(end_y), Included(start_y))) {
-                results.push(life.clone());
+                results.push(*life);
             }
         }
-        return results;
+        results
     }
 
     fn query_around_point(&self, x: i64, y: i64) -> Vec<Life> {
-        return self.spatial_query(
+        self.spatial_query(
             i64::saturating_sub(x, 2),
             i64::saturating_add(y, 2),
             i64::saturating_add(x, 2),
-            i64::saturating_sub(y, 2));
+            i64::saturating_sub(y, 2))
+    }
+
+    pub fn age(&self) -> u32 {
+        self.age
+    }
+
+    pub fn live_count(&self) -> usize {
+        let x_map = self.map.read().unwrap();
+        x_map.values().map(|y_map| y_map.len()).sum()
     }
 
     pub fn initialize(&self) {
@@ -131,10 +139,48 @@ impl TimeBasedEntity for Life {
     }
 }
 
+/// Decides the fate of a single live `life`: whether it dies this
+/// generation, and the coordinates of any new cells its neighborhood gives
+/// birth to. Shared by the serial and tiled-parallel stepping paths since
+/// it only reads the world within two cells of `life` (its own 3x3 window
+/// for survival, the 5x5 window around it for births).
+fn evaluate_cell(world: &World, life: &Life) -> (bool, Vec<(i64, i64)>) {
+    let mut close_neighbor_count: u8 = 0;
+
+    // Initialize to 1 to account for self
+    let mut new_life_counters: [u8; 8] = [1; 8];
+
+    for neighbor in world.query_around_point(life.x_pos, life.y_pos) {
+        // Skip Self
+        if life.x_pos == neighbor.x_pos && life.y_pos == neighbor.y_pos {
+            continue;
+        }
+        // Skip newborns
+        if neighbor.age == 0 {
+            continue;
+        }
+
+        if life.is_close_neighbor(&neighbor) {
+            close_neighbor_count += 1;
+        }
+
+        update_new_life_counters(&mut new_life_counters, life, &neighbor);
+    }
+
+    let mut births = Vec::new();
+    for (i, counter) in new_life_counters.iter().enumerate() {
+        if world.rule.is_born(*counter) {
+            if let Some(coords) = life.calculate_neighbor_coordinates(i as u8) {
+                births.push(coords);
+            }
+        }
+    }
+
+    (!world.rule.survives(close_neighbor_count), births)
+}
+
 impl TimeBasedEntity for World {
     fn tick(&mut self) {
-        let mut new_life_counters:[u8; 8];
-
         let mut kill_vec: Vec<Life> = Vec::new();
         let mut new_life_set: HashSet<(i64, i64)> = HashSet::new();
 
@@ -148,39 +194,11 @@ impl TimeBasedEntity for World {
                         continue;
                     }
 
-                    let mut close_neighbor_count: u8 = 0;
-
-                    // Initialize to 1 to account for self
-                    new_life_counters = [1; 8];
-
-                    for neighbor in self.query_around_point(life.x_pos, life.y_pos) {
-                        // Skip Self
-                        if life.x_pos == neighbor.x_pos && life.y_pos == neighbor.y_pos {
-                            continue;
-                        }
-                        // Skip newborns
-                        if neighbor.age == 0 {
-                            continue;
-                        }
-
-                        if life.is_close_neighbor(&neighbor) {
-                            close_neighbor_count += 1;
-                        }
-
-                        update_new_life_counters(&mut new_life_counters, life, &neighbor);
-                    }
-
-                    for (i, counter) in new_life_counters.iter().enumerate() {
-                        if *counter == 3 {
-                            match life.calculate_neighbor_coordinates(i as u8) {
-                                Some(coords) => new_life_set.insert(coords),
-                                None => false
-                            };
-                        }
-                    }
+                    let (dies, births) = evaluate_cell(self, life);
+                    new_life_set.extend(births);
 
-                    if !(close_neighbor_count == 2 || close_neighbor_count == 3) {
-                        kill_vec.push(life.clone());
+                    if dies {
+                        kill_vec.push(*life);
                     }
                 }
             }
@@ -205,45 +223,104 @@ impl TimeBasedEntity for World {
     }
 }
 
-fn main() {
-    let mut world: World = World { map: RwLock::new(BTreeMap::new()), age: 0 };
-
-    let mut lineno = 0;
-    for line_result in io::stdin().lock().lines() {
-        match line_result {
-            Ok(line) => {
-                let clean_line = line.trim();
-                if lineno == 0 {
-                    if !clean_line.eq("#Life 1.06") {
-                        println!("File is not a valid Life 1.06 file, does not begin with proper header");
-                        exit(0)
-                    } else {
-                        lineno += 1;
-                        continue;
-                    }
-                }
-                let mut parts: Vec<&str> = clean_line.split(" ").collect();
-                let y_str = parts.pop().unwrap();
-                let x_str = parts.pop().unwrap();
-                let x_pos = i64::from_str(x_str).unwrap();
-                let y_pos = i64::from_str(y_str).unwrap();
+fn run_headless(world: &mut World, ticks: u32, threads: usize) {
+    for _ in 0..ticks {
+        parallel::tick_parallel(world, threads);
+    }
+}
 
-                world.add_life(Life::new(x_pos, y_pos));
-            }
-            Err(_) => {
-                exit(0)
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let tui_mode = args.iter().any(|arg| arg == "--tui");
+    let output_format = args
+        .iter()
+        .position(|arg| arg == "--output-format")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| formats::Format::from_str(v).ok());
+    // `None` means the user didn't ask for a specific rule, leaving room
+    // for a format that carries its own (RLE's `rule = ...` header) to win.
+    let explicit_rule: Option<Rule> = args
+        .iter()
+        .position(|arg| arg == "--rule")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| Rule::from_str(v).ok());
+    let hashlife_engine = args
+        .iter()
+        .position(|arg| arg == "--engine")
+        .and_then(|i| args.get(i + 1))
+        .map(|v| v == "hashlife")
+        .unwrap_or(false);
+    // Shared by both engines: the hashlife path treats it as a macro-step
+    // generation count (any value, default 8); the serial and tiled-parallel
+    // path treats it as a plain tick count (default 10).
+    let generations_arg: Option<u64> = args
+        .iter()
+        .position(|arg| arg == "--generations")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse().ok());
+    let threads: usize = args
+        .iter()
+        .position(|arg| arg == "--threads")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1);
+    let load_path = args
+        .iter()
+        .position(|arg| arg == "--load")
+        .and_then(|i| args.get(i + 1))
+        .cloned();
+    let save_path = args
+        .iter()
+        .position(|arg| arg == "--save")
+        .and_then(|i| args.get(i + 1))
+        .cloned();
+
+    let (mut world, detected_format, resumed) = match &load_path {
+        Some(path) => match persistence::load(path, explicit_rule.clone().unwrap_or_default()) {
+            Ok(world) => (world, formats::Format::Life106, true),
+            Err(err) => {
+                println!("Could not load snapshot '{}': {}", path, err);
+                exit(1)
             }
+        },
+        None => {
+            let (world, format) = formats::parse(io::stdin().lock(), explicit_rule);
+            (world, format, false)
         }
+    };
+    let output_format = output_format.unwrap_or(detected_format);
+
+    // A loaded world already has live ages; only a freshly-parsed one needs
+    // the age-0 -> 1 priming so its first tick doesn't treat every cell as a
+    // newborn.
+    if !resumed {
+        world.initialize();
     }
 
-    world.initialize();
+    if tui_mode {
+        tui::run(world);
+        return;
+    }
 
-    for _ in 0..10 {
-        world.tick();
+    if hashlife_engine {
+        let resolved_rule = world.rule.clone();
+        let universe = hashlife::Universe::new(resolved_rule.clone());
+        let (root, x0, y0) = universe.from_world(&world);
+        let (advanced, x0, y0) = universe.step(root, x0, y0, generations_arg.unwrap_or(8));
+        world = universe.to_world(&advanced, x0, y0, resolved_rule);
+    } else {
+        let ticks = generations_arg.map(|g| g as u32).unwrap_or(10);
+        run_headless(&mut world, ticks, threads);
+    }
+
+    if let Some(path) = &save_path {
+        if let Err(err) = persistence::save(&world, path) {
+            println!("Could not save snapshot '{}': {}", path, err);
+            exit(1)
+        }
     }
 
-    println!("#Life 1.06");
-    println!("{}", world);
+    print!("{}", formats::serialize(&world, output_format));
 }
 
 fn update_new_life_counters(counters: &mut [u8; 8], center: &Life, neighbor: &Life) {
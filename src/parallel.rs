@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+use std::thread;
+
+use crate::{evaluate_cell, Life, TimeBasedEntity, World};
+
+/// Per-tile result of `process_tile`: the cells that die this generation and
+/// the coordinates of any newborns.
+type TileResult = (Vec<Life>, HashSet<(i64, i64)>);
+
+fn bounding_x_range(world: &World) -> Option<(i64, i64)> {
+    let cells = world.spatial_query(i64::MIN, i64::MAX, i64::MAX, i64::MIN);
+    if cells.is_empty() {
+        return None;
+    }
+    let min_x = cells.iter().map(|life| life.x_pos).min().unwrap();
+    let max_x = cells.iter().map(|life| life.x_pos).max().unwrap();
+    Some((min_x, max_x))
+}
+
+fn tile_ranges(min_x: i64, max_x: i64, tiles: usize) -> Vec<(i64, i64)> {
+    let span = max_x - min_x + 1;
+    let tile_width = (span / tiles as i64).max(1);
+
+    (0..tiles)
+        .map(|i| {
+            let start = min_x + i as i64 * tile_width;
+            let end = if i == tiles - 1 { max_x } else { start + tile_width - 1 };
+            (start, end)
+        })
+        .filter(|&(start, end)| start <= end)
+        .collect()
+}
+
+/// Evaluates every live, non-newborn cell whose x coordinate falls in
+/// `[start, end]`, reading neighbors (including the two-cell halo outside
+/// the tile that `evaluate_cell` looks at) through `World::spatial_query`.
+fn process_tile(world: &World, start: i64, end: i64) -> TileResult {
+    let mut kill_vec = Vec::new();
+    let mut new_life_set = HashSet::new();
+
+    for life in world.spatial_query(start, i64::MAX, end, i64::MIN) {
+        if life.age == 0 {
+            continue;
+        }
+
+        let (dies, births) = evaluate_cell(world, &life);
+        new_life_set.extend(births);
+
+        if dies {
+            kill_vec.push(life);
+        }
+    }
+
+    (kill_vec, new_life_set)
+}
+
+/// Steps `world` one generation, partitioning its occupied x-range into
+/// `threads` tiles and evaluating them concurrently before merging the
+/// resulting deaths and births under a single write lock. Falls back to
+/// the serial `World::tick` when `threads <= 1` or the world is empty.
+pub fn tick_parallel(world: &mut World, threads: usize) {
+    if threads <= 1 {
+        world.tick();
+        return;
+    }
+
+    let (min_x, max_x) = match bounding_x_range(world) {
+        Some(bounds) => bounds,
+        None => {
+            world.tick();
+            return;
+        }
+    };
+
+    let ranges = tile_ranges(min_x, max_x, threads);
+    let world_ref: &World = world;
+
+    let tile_results: Vec<TileResult> = thread::scope(|scope| {
+        let handles: Vec<_> = ranges
+            .iter()
+            .map(|&(start, end)| scope.spawn(move || process_tile(world_ref, start, end)))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut kill_vec = Vec::new();
+    let mut new_life_set = HashSet::new();
+    for (tile_kills, tile_births) in tile_results {
+        kill_vec.extend(tile_kills);
+        new_life_set.extend(tile_births);
+    }
+
+    for life in kill_vec {
+        world.remove_life(&life);
+    }
+    for coords in new_life_set {
+        world.add_life(Life::new(coords.0, coords.1));
+    }
+
+    let mut x_map = world.map.write().unwrap();
+    for (_x_index, y_map) in x_map.iter_mut() {
+        for (_y_index, life) in y_map.iter_mut() {
+            life.tick();
+        }
+    }
+    drop(x_map);
+
+    world.age += 1;
+}
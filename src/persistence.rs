@@ -0,0 +1,62 @@
+use std::convert::TryInto;
+
+use crate::{BTreeMap, Life, Rule, RwLock, World};
+
+const AGE_KEY: &[u8] = b"__world_age__";
+
+fn pack_coords(x: i64, y: i64) -> [u8; 16] {
+    let mut key = [0u8; 16];
+    key[0..8].copy_from_slice(&x.to_be_bytes());
+    key[8..16].copy_from_slice(&y.to_be_bytes());
+    key
+}
+
+fn unpack_coords(key: &[u8]) -> (i64, i64) {
+    let x = i64::from_be_bytes(key[0..8].try_into().unwrap());
+    let y = i64::from_be_bytes(key[8..16].try_into().unwrap());
+    (x, y)
+}
+
+/// Checkpoints `world` to the on-disk key-value store at `path`, keyed by
+/// packed `(x, y)` coordinates. Per-cell `age` and the generation counter
+/// both survive the round trip; the world's rule is not stored, so a reload
+/// ticks identically only when `load` is given the same rule.
+pub fn save(world: &World, path: &str) -> sled::Result<()> {
+    let db = sled::open(path)?;
+    db.clear()?;
+
+    let x_map = world.map.read().unwrap();
+    for (_x_key, y_map) in x_map.iter() {
+        for (_y_key, life) in y_map.iter() {
+            db.insert(pack_coords(life.x_pos, life.y_pos), &life.age.to_be_bytes())?;
+        }
+    }
+    drop(x_map);
+
+    db.insert(AGE_KEY, &world.age.to_be_bytes())?;
+    db.flush()?;
+    Ok(())
+}
+
+/// Rebuilds a `World` from a snapshot written by `save`. The snapshot holds
+/// no rule; the rebuilt world runs under `rule`.
+pub fn load(path: &str, rule: Rule) -> sled::Result<World> {
+    let db = sled::open(path)?;
+    let mut world = World { map: RwLock::new(BTreeMap::new()), age: 0, rule };
+
+    for entry in db.iter() {
+        let (key, value) = entry?;
+
+        if key.as_ref() == AGE_KEY {
+            world.age = u32::from_be_bytes(value.as_ref().try_into().unwrap());
+            continue;
+        }
+
+        let (x_pos, y_pos) = unpack_coords(&key);
+        let mut life = Life::new(x_pos, y_pos);
+        life.age = u32::from_be_bytes(value.as_ref().try_into().unwrap());
+        world.add_life(life);
+    }
+
+    Ok(world)
+}
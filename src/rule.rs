@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A life-like cellular automaton rule in standard `B/S` notation, e.g.
+/// `B3/S23` for Conway's Life or `B36/S23` for HighLife.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    births: HashSet<u8>,
+    survives: HashSet<u8>,
+}
+
+impl Rule {
+    pub fn conway() -> Rule {
+        Rule::from_str("B3/S23").unwrap()
+    }
+
+    pub fn is_born(&self, neighbor_count: u8) -> bool {
+        self.births.contains(&neighbor_count)
+    }
+
+    pub fn survives(&self, neighbor_count: u8) -> bool {
+        self.survives.contains(&neighbor_count)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Rule {
+        Rule::conway()
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut births: Vec<&u8> = self.births.iter().collect();
+        births.sort_unstable();
+        let mut survives: Vec<&u8> = self.survives.iter().collect();
+        survives.sort_unstable();
+
+        write!(f, "B")?;
+        for n in births {
+            write!(f, "{}", n)?;
+        }
+        write!(f, "/S")?;
+        for n in survives {
+            write!(f, "{}", n)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rule, String> {
+        let mut parts = s.splitn(2, '/');
+        let b_part = parts.next().unwrap_or("");
+        let s_part = parts.next().unwrap_or("");
+
+        let births = parse_digits(b_part, 'B')?;
+        let survives = parse_digits(s_part, 'S')?;
+
+        Ok(Rule { births, survives })
+    }
+}
+
+fn parse_digits(part: &str, prefix: char) -> Result<HashSet<u8>, String> {
+    let digits = part
+        .strip_prefix(prefix)
+        .ok_or_else(|| format!("rule part '{}' must start with '{}'", part, prefix))?;
+
+    let mut set = HashSet::new();
+    for ch in digits.chars() {
+        let n = ch
+            .to_digit(10)
+            .ok_or_else(|| format!("invalid digit '{}' in rule part '{}'", ch, part))?;
+        set.insert(n as u8);
+    }
+    Ok(set)
+}
@@ -0,0 +1,190 @@
+use std::io;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Terminal;
+
+use crate::{TimeBasedEntity, World};
+
+enum Event {
+    Tick,
+    Input(KeyEvent),
+}
+
+struct Viewport {
+    cursor_x: i64,
+    cursor_y: i64,
+    /// World cells per character in each direction; each rendered character
+    /// represents a `scale x scale` block, lit if any cell in it is alive.
+    /// `1` is full detail; larger values zoom out to see more of the world
+    /// in the same terminal area.
+    scale: i64,
+}
+
+impl Viewport {
+    fn new() -> Viewport {
+        Viewport { cursor_x: 0, cursor_y: 0, scale: 1 }
+    }
+
+    fn pan(&mut self, dx: i64, dy: i64) {
+        let step = self.scale;
+        self.cursor_x = i64::saturating_add(self.cursor_x, dx * step);
+        self.cursor_y = i64::saturating_add(self.cursor_y, dy * step);
+    }
+
+    fn zoom(&mut self, delta: i64) {
+        self.scale = (self.scale + delta).max(1);
+    }
+}
+
+/// Terminal setup/teardown as an RAII guard, so the alternate screen and raw
+/// mode are always restored on the way out — including when a draw-loop
+/// `.unwrap()` panics, since `Drop` still runs while the stack unwinds.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> io::Result<TerminalGuard> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+fn spawn_input_thread() -> mpsc::Receiver<Event> {
+    let (tx, rx) = mpsc::channel();
+    let tick_rate = Duration::from_millis(200);
+
+    thread::spawn(move || {
+        loop {
+            if event::poll(tick_rate).unwrap_or(false) {
+                if let Ok(CrosstermEvent::Key(key)) = event::read() {
+                    if tx.send(Event::Input(key)).is_err() {
+                        return;
+                    }
+                    continue;
+                }
+            }
+            if tx.send(Event::Tick).is_err() {
+                return;
+            }
+        }
+    });
+
+    rx
+}
+
+fn render_viewport(world: &World, view: &Viewport, area: Rect) -> Paragraph<'static> {
+    let scale = view.scale.max(1);
+    let rows = area.height.max(1) as i64;
+    let cols = area.width.max(1) as i64;
+
+    let half_width_cells = (cols / 2).max(1) * scale;
+    let half_height_cells = (rows / 2).max(1) * scale;
+    let start_x = i64::saturating_sub(view.cursor_x, half_width_cells);
+    let end_x = i64::saturating_add(view.cursor_x, half_width_cells);
+    let start_y = i64::saturating_add(view.cursor_y, half_height_cells);
+    let end_y = i64::saturating_sub(view.cursor_y, half_height_cells);
+
+    let live = world.spatial_query(start_x, start_y, end_x, end_y);
+    let mut alive: std::collections::HashSet<(i64, i64)> = std::collections::HashSet::new();
+    for life in live {
+        alive.insert((life.x_pos, life.y_pos));
+    }
+
+    let mut lines: Vec<Line> = Vec::with_capacity(rows as usize);
+
+    for row in 0..rows {
+        let block_y0 = end_y + (rows - 1 - row) * scale;
+        let mut spans: Vec<Span> = Vec::with_capacity(cols as usize);
+        for col in 0..cols {
+            let block_x0 = start_x + col * scale;
+            let has_life = (0..scale)
+                .any(|dy| (0..scale).any(|dx| alive.contains(&(block_x0 + dx, block_y0 + dy))));
+            if has_life {
+                spans.push(Span::styled("#", Style::default().fg(Color::Green)));
+            } else {
+                spans.push(Span::raw(" "));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("life-rust"))
+}
+
+fn render_status(world: &World, view: &Viewport, paused: bool) -> Paragraph<'static> {
+    let text = format!(
+        "age={} live={} cursor=({}, {}) zoom={}x {}",
+        world.age(),
+        world.live_count(),
+        view.cursor_x,
+        view.cursor_y,
+        view.scale,
+        if paused { "[paused]" } else { "[running]" },
+    );
+    Paragraph::new(text)
+}
+
+/// Runs the interactive terminal UI for `world` until the user quits. The
+/// alternate screen and raw mode are entered up front and always restored on
+/// the way out, including on a panic, via `TerminalGuard`'s `Drop` impl.
+pub fn run(mut world: World) {
+    let _guard = TerminalGuard::new().unwrap();
+    let backend = CrosstermBackend::new(io::stdout());
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    let events = spawn_input_thread();
+    let mut view = Viewport::new();
+    let mut paused = false;
+
+    loop {
+        terminal
+            .draw(|f| {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(1)])
+                    .split(f.size());
+
+                f.render_widget(render_viewport(&world, &view, chunks[0]), chunks[0]);
+                f.render_widget(render_status(&world, &view, paused), chunks[1]);
+            })
+            .unwrap();
+
+        match events.recv() {
+            Ok(Event::Tick) => {
+                if !paused {
+                    world.tick();
+                }
+            }
+            Ok(Event::Input(key)) => match key.code {
+                KeyCode::Char('q') => break,
+                KeyCode::Char(' ') => paused = !paused,
+                KeyCode::Char('n') => world.tick(),
+                KeyCode::Char('+') => view.zoom(-1),
+                KeyCode::Char('-') => view.zoom(1),
+                KeyCode::Up => view.pan(0, 1),
+                KeyCode::Down => view.pan(0, -1),
+                KeyCode::Left => view.pan(-1, 0),
+                KeyCode::Right => view.pan(1, 0),
+                _ => {}
+            },
+            Err(_) => break,
+        }
+    }
+}